@@ -77,8 +77,22 @@ export default function CreatePage() {
         throw new Error("Profit share must be between 1-100%");
       }
 
-      // For smart contract compatibility, we pass stake amount as the "reward"
-      const id = await createStrategy(publicKey, stake, signTx);
+      // For smart contract compatibility, we pass stake amount as the "reward".
+      // The form does not ask for tags, timeframe or risk yet, so those
+      // discovery fields get neutral defaults.
+      const id = await createStrategy(
+        publicKey,
+        stake,
+        filledRules,
+        {
+          tags: ["general"],
+          base: baseAsset.trim() || "BTC",
+          quote: counterAsset.trim() || "USDC",
+          timeframe: "Intraday",
+          riskLevel: "Medium",
+        },
+        signTx
+      );
       setStrategyId(id);
 
       // Save off-chain metadata (title, description, rules, stake, profit share, trading pair)
@@ -129,7 +129,8 @@ export default function DisputePage() {
     setError(null);
 
     try {
-      const dId = await raiseDispute(publicKey, executionId, selectedReason, signTx);
+      const evidence = `${DISPUTE_REASONS[selectedReason]?.label}: ${disputeDetails}`;
+      const dId = await raiseDispute(publicKey, executionId, selectedReason, evidence, null, signTx);
       setDisputeId(dId);
 
       // Now trigger secondary AI review
@@ -146,7 +147,7 @@ export default function DisputePage() {
         body: JSON.stringify({
           strategy: strategyForAI,
           execution: executionForAI,
-          disputeReason: `${DISPUTE_REASONS[selectedReason]?.label}: ${disputeDetails}`,
+          disputeReason: evidence,
         }),
       });
 
@@ -155,7 +156,7 @@ export default function DisputePage() {
       setResolutionResult(result);
 
       // Resolve on-chain based on AI dispute review
-      await resolveDispute(publicKey, executionId, result.upheld, signTx);
+      await resolveDispute(publicKey, dId, result.upheld, signTx);
 
       // Reload execution state
       const exec = await getExecution(executionId);
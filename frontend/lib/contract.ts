@@ -11,6 +11,15 @@ import {
   toScValU64,
   toScValI128,
   toScValBool,
+  toScValU32,
+  toScValString,
+  toScValOption,
+  toScValSymbol,
+  toScValBytes,
+  toScValVec,
+  toScValEnum,
+  toScValStruct,
+  sha256,
   scValToU64,
   scValToI128,
   scValToBool,
@@ -22,19 +31,51 @@ type SignFn = (
   opts: { networkPassphrase: string }
 ) => Promise<{ signedTxXdr: string }>;
 
+// Mirrors the contract's StrategyProfile (discovery fields)
+export type Timeframe = "Scalp" | "Intraday" | "Swing" | "Position";
+export type RiskLevel = "Low" | "Medium" | "High";
+
+export interface StrategyProfile {
+  tags: string[]; // 1-5 distinct symbols (letters, digits, _)
+  base: string;
+  quote: string;
+  timeframe: Timeframe;
+  riskLevel: RiskLevel;
+}
+
 /**
- * Create a new strategy on-chain.
+ * Create a new strategy on-chain, open to any executor.
+ * The rules are stored off-chain; only their sha256 goes on-chain.
  * Returns the strategy_id.
  */
 export async function createStrategy(
   publicKey: string,
   rewardAmount: number,
+  rules: string[],
+  profile: StrategyProfile,
   signTransaction: SignFn
 ): Promise<number> {
+  const rulesHash = await sha256(rules.join("\n"));
+  const profileArg = toScValStruct({
+    tags: toScValVec(profile.tags.map(toScValSymbol)),
+    asset_pair: toScValStruct({
+      base: toScValSymbol(profile.base),
+      quote: toScValSymbol(profile.quote),
+    }),
+    timeframe: toScValEnum(profile.timeframe),
+    risk_level: toScValEnum(profile.riskLevel),
+  });
+
   const response = await invokeContract(
     publicKey,
     "create_strategy",
-    [toScValAddress(publicKey), toScValI128(rewardAmount)],
+    [
+      toScValAddress(publicKey),
+      toScValI128(rewardAmount),
+      toScValBytes(rulesHash),
+      profileArg,
+      toScValEnum("Open"),
+    ],
     signTransaction
   );
 
@@ -45,17 +86,23 @@ export async function createStrategy(
 
 /**
  * Submit an execution for a strategy.
+ * merkleProof is only needed for Merkle-gated strategies.
  * Returns the execution_id.
  */
 export async function submitExecution(
   publicKey: string,
   strategyId: number,
-  signTransaction: SignFn
+  signTransaction: SignFn,
+  merkleProof: Uint8Array[] = []
 ): Promise<number> {
   const response = await invokeContract(
     publicKey,
     "submit_execution",
-    [toScValAddress(publicKey), toScValU64(strategyId)],
+    [
+      toScValAddress(publicKey),
+      toScValU64(strategyId),
+      toScValVec(merkleProof.map(toScValBytes)),
+    ],
     signTransaction
   );
 
@@ -104,22 +151,39 @@ export async function setConfidence(
   );
 }
 
+// Mirrors the contract's DisputeReason (encoded as u32 on the wire)
+export enum DisputeReason {
+  FakeProof = 1,
+  Incomplete = 2,
+  Plagiarized = 3,
+}
+
 /**
  * Raise a dispute as the strategy creator.
  * Can only be called during the dispute window after AI approval.
- * reason_code: 1=fake_proof, 2=incomplete, 3=plagiarized
+ * The evidence text stays off-chain; only its sha256 goes on-chain,
+ * with an optional URI where it can be fetched.
  * Returns dispute_id.
  */
 export async function raiseDispute(
   publicKey: string,
   executionId: number,
-  reasonCode: number,
+  reason: DisputeReason,
+  evidence: string,
+  evidenceUri: string | null,
   signTransaction: SignFn
 ): Promise<number> {
+  const evidenceHash = await sha256(evidence);
   const response = await invokeContract(
     publicKey,
     "raise_dispute",
-    [toScValAddress(publicKey), toScValU64(executionId), toScValU64(reasonCode)],
+    [
+      toScValAddress(publicKey),
+      toScValU64(executionId),
+      toScValU32(reason),
+      toScValBytes(evidenceHash),
+      toScValOption(evidenceUri ? toScValString(evidenceUri) : null),
+    ],
     signTransaction
   );
 
@@ -129,20 +193,21 @@ export async function raiseDispute(
 }
 
 /**
- * Resolve a dispute. Called after secondary AI review.
+ * Resolve a dispute by id. Called after secondary AI review by an
+ * arbiter registered at the dispute's current tier (publicKey).
  * upheld=true → trader slashed, expert refunded
  * upheld=false → execution cleared, stream starts
  */
 export async function resolveDispute(
   publicKey: string,
-  executionId: number,
+  disputeId: number,
   upheld: boolean,
   signTransaction: SignFn
 ): Promise<boolean> {
   const response = await invokeContract(
     publicKey,
     "resolve_dispute",
-    [toScValU64(executionId), toScValBool(upheld)],
+    [toScValAddress(publicKey), toScValU64(disputeId), toScValBool(upheld)],
     signTransaction
   );
 
@@ -311,7 +376,8 @@ function parseExecStatus(status: any): string {
 export interface OnChainDispute {
   challenger: string;
   execution_id: number;
-  reason_code: number;
+  reason: DisputeReason;
+  evidence_hash: string; // hex
   created_at: number;
   resolved: boolean;
   upheld: boolean;
@@ -328,7 +394,10 @@ export async function getDispute(disputeId: number): Promise<OnChainDispute | nu
     return {
       challenger: parsed.challenger?.toString?.() || parsed.challenger || "",
       execution_id: Number(parsed.execution_id || 0),
-      reason_code: Number(parsed.reason_code || 0),
+      reason: Number(parsed.reason || 0),
+      evidence_hash: Array.from(parsed.evidence_hash || [], (b: number) =>
+        b.toString(16).padStart(2, "0")
+      ).join(""),
       created_at: Number(parsed.created_at || 0),
       resolved: Boolean(parsed.resolved),
       upheld: Boolean(parsed.upheld),
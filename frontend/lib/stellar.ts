@@ -178,6 +178,63 @@ export function toScValString(value: string): StellarSdk.xdr.ScVal {
   return StellarSdk.nativeToScVal(value, { type: "string" });
 }
 
+export function toScValU32(value: number): StellarSdk.xdr.ScVal {
+  return StellarSdk.nativeToScVal(value, { type: "u32" });
+}
+
+export function toScValSymbol(value: string): StellarSdk.xdr.ScVal {
+  return StellarSdk.nativeToScVal(value, { type: "symbol" });
+}
+
+export function toScValBytes(value: Uint8Array): StellarSdk.xdr.ScVal {
+  return StellarSdk.nativeToScVal(value, { type: "bytes" });
+}
+
+export function toScValVec(values: StellarSdk.xdr.ScVal[]): StellarSdk.xdr.ScVal {
+  return StellarSdk.xdr.ScVal.scvVec(values);
+}
+
+export function toScValOption(value: StellarSdk.xdr.ScVal | null): StellarSdk.xdr.ScVal {
+  return value ?? StellarSdk.xdr.ScVal.scvVoid();
+}
+
+/**
+ * Encode a Soroban enum variant: unit variants are [name], tuple
+ * variants are [name, ...fields].
+ */
+export function toScValEnum(
+  variant: string,
+  fields: StellarSdk.xdr.ScVal[] = []
+): StellarSdk.xdr.ScVal {
+  return StellarSdk.xdr.ScVal.scvVec([toScValSymbol(variant), ...fields]);
+}
+
+/**
+ * Encode a Soroban struct as a map keyed by field name (sorted, as
+ * the host requires).
+ */
+export function toScValStruct(
+  fields: Record<string, StellarSdk.xdr.ScVal>
+): StellarSdk.xdr.ScVal {
+  return StellarSdk.xdr.ScVal.scvMap(
+    Object.keys(fields)
+      .sort()
+      .map(
+        (key) =>
+          new StellarSdk.xdr.ScMapEntry({ key: toScValSymbol(key), val: fields[key] })
+      )
+  );
+}
+
+/**
+ * sha256 of a UTF-8 string, for on-chain BytesN<32> hashes of
+ * off-chain documents.
+ */
+export async function sha256(text: string): Promise<Uint8Array> {
+  const digest = await crypto.subtle.digest("SHA-256", new TextEncoder().encode(text));
+  return new Uint8Array(digest);
+}
+
 /**
  * Read-only contract query (no signing needed).
  * Uses simulateTransaction to call view functions.